@@ -6,9 +6,12 @@ use panic_halt as _;
 
 // String formatting
 use cmim::{
-    Move,
+    bind_move,
     Context,
     Exception,
+    InterruptBinding,
+    InterruptState,
+    Move,
 };
 
 // Used to set the program entry point
@@ -37,6 +40,35 @@ struct Timer1Data {
     toggle: bool,
 }
 
+impl InterruptState for Timer1Data {
+    fn on_interrupt(&mut self) {
+        // Start the timer again first for accuracy
+        self.timer.cancel().unwrap();
+        self.timer.start(1_000_000u32);
+
+        // Write message to UART. The NRF UART requires data
+        // to be in RAM, not flash.
+        const MSG_BYTES: &[u8] = "Blink!\r\n".as_bytes();
+        let mut buf = [0u8; MSG_BYTES.len()];
+        buf.copy_from_slice(MSG_BYTES);
+
+        self.uart.write(&buf).unwrap();
+
+        // Blink the LED
+        if self.toggle {
+            self.led.enable();
+        } else {
+            self.led.disable();
+        }
+
+        self.toggle = !self.toggle;
+    }
+}
+
+unsafe impl InterruptBinding for Timer1Data {
+    const VECTOR: &'static str = "TIMER1";
+}
+
 struct SysTickData {
     led: dwm1001::Led,
     toggle: bool,
@@ -112,31 +144,4 @@ fn SysTick() {
         .unwrap();
 }
 
-#[interrupt]
-fn TIMER1() {
-    TIMER_1_DATA
-        .try_lock(|data| {
-            // Start the timer again first for accuracy
-            data.timer.cancel().unwrap();
-            data.timer.start(1_000_000u32);
-
-            // Write message to UART. The NRF UART requires data
-            // to be in RAM, not flash.
-            const MSG_BYTES: &[u8] = "Blink!\r\n".as_bytes();
-            let mut buf = [0u8; MSG_BYTES.len()];
-            buf.copy_from_slice(MSG_BYTES);
-
-            data.uart.write(&buf).unwrap();
-
-            // Blink the LED
-            if data.toggle {
-                data.led.enable();
-            } else {
-                data.led.disable();
-            }
-
-            data.toggle = !data.toggle;
-        })
-        .map_err(drop)
-        .unwrap();
-}
+bind_move!(TIMER1, Timer1Data => TIMER_1_DATA);
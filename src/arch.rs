@@ -0,0 +1,115 @@
+//! Architecture-specific backends for [`Move`](crate::Move).
+//!
+//! `cmim` needs exactly two things from an architecture: whether we're
+//! currently outside of any interrupt/exception context ("thread mode"), and
+//! whether a given [`Context`] is the one currently active. [`ContextArch`]
+//! factors those two operations behind a trait so `Move` isn't hard-wired to
+//! Cortex-M's `SCB`.
+
+use crate::Context;
+#[cfg(any(feature = "cortex-m", feature = "riscv"))]
+use bare_metal::Nr;
+
+/// The operations [`Move`](crate::Move) needs from an architecture in order
+/// to know which interrupt/exception is currently active.
+pub trait ContextArch<I> {
+    /// Returns `true` if we are outside of any interrupt/exception context
+    /// (e.g. Cortex-M "thread mode").
+    fn is_thread_mode() -> bool;
+
+    /// Returns `true` if `ctxt` names the currently active interrupt/exception.
+    fn is_active(ctxt: &Context<I>) -> bool;
+}
+
+/// ARM Cortex-M backend, built on `cortex_m::peripheral::SCB`. This is the
+/// default backend, for source compatibility with `Move<T, I>` used
+/// throughout earlier versions of this crate.
+#[cfg(feature = "cortex-m")]
+pub struct CortexM;
+
+#[cfg(feature = "cortex-m")]
+mod cortex_m_backend {
+    use super::*;
+    use cortex_m::peripheral::{scb::VectActive, SCB};
+
+    impl<I: Nr> PartialEq<VectActive> for Context<I> {
+        fn eq(&self, other: &VectActive) -> bool {
+            match (self, other) {
+                (Context::Exception(e_s), VectActive::Exception(e_o)) => e_s == e_o,
+                (Context::Interrupt(i_s), VectActive::Interrupt { irqn }) => i_s.nr() == *irqn,
+                _ => false,
+            }
+        }
+    }
+
+    impl<I: Nr> ContextArch<I> for CortexM {
+        fn is_thread_mode() -> bool {
+            matches!(SCB::vect_active(), VectActive::ThreadMode)
+        }
+
+        fn is_active(ctxt: &Context<I>) -> bool {
+            *ctxt == SCB::vect_active()
+        }
+    }
+}
+
+/// RISC-V backend targeting a Platform-Level Interrupt Controller (PLIC), as
+/// found on the RP2040's second core and many other RISC-V SoCs.
+///
+/// Unlike Cortex-M's `SCB`, a PLIC doesn't expose a single "currently active
+/// vector" register: instead, a hart claims an IRQ from the PLIC before
+/// running its handler, and completes it afterwards. [`Plic::is_active`]
+/// treats "the IRQ most recently claimed by this hart, and not yet
+/// completed" as the active context. Wiring `claim`/`complete` up to the
+/// real PLIC claim/complete registers is left to a PAC-provided driver; this
+/// module only provides the bookkeeping seam `cmim` needs.
+#[cfg(feature = "riscv")]
+pub struct Plic;
+
+#[cfg(feature = "riscv")]
+pub mod plic {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    /// Sentinel meaning "no IRQ is currently claimed by this hart".
+    const NONE: u32 = u32::MAX;
+
+    static CLAIMED_IRQ: AtomicU32 = AtomicU32::new(NONE);
+
+    /// Record that `irqn` has been claimed from the PLIC and is now running.
+    /// Call this at the top of the PLIC's generic handler, after claiming.
+    pub fn set_claimed(irqn: u32) {
+        CLAIMED_IRQ.store(irqn, Ordering::Release);
+    }
+
+    /// Record that the previously-claimed IRQ has completed. Call this after
+    /// writing the PLIC's completion register, before returning from the
+    /// handler.
+    pub fn clear_claimed() {
+        CLAIMED_IRQ.store(NONE, Ordering::Release);
+    }
+
+    /// The IRQ number currently claimed by this hart, if any.
+    pub fn claimed() -> Option<u32> {
+        match CLAIMED_IRQ.load(Ordering::Acquire) {
+            NONE => None,
+            irqn => Some(irqn),
+        }
+    }
+}
+
+#[cfg(feature = "riscv")]
+impl<I: Nr> ContextArch<I> for Plic {
+    fn is_thread_mode() -> bool {
+        plic::claimed().is_none()
+    }
+
+    fn is_active(ctxt: &Context<I>) -> bool {
+        match ctxt {
+            Context::Interrupt(i) => plic::claimed() == Some(i.nr() as u32),
+            // `Context::Exception` only exists when the `cortex-m` feature is
+            // also enabled; a PLIC never claims one, so it's never active.
+            #[cfg(feature = "cortex-m")]
+            Context::Exception(_) => false,
+        }
+    }
+}
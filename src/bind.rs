@@ -0,0 +1,133 @@
+//! Compile-time binding of a [`Move`](crate::Move) to the interrupt handler
+//! that drives it.
+//!
+//! Without this, nothing checks that the `Context` passed to
+//! `Move::new_uninitialized` matches the interrupt you actually write the
+//! `#[interrupt]` for -- a mismatch only shows up at runtime as `try_lock`
+//! returning `Err(())`. [`bind_move!`] generates that handler for you, and
+//! [`InterruptBinding`] turns a mismatch between the generated handler's
+//! vector and the data type's declared `VECTOR` into a compile error
+//! instead. The `Move`'s `Context` itself is not checked: the `unsafe`
+//! [`InterruptBinding`] impl vouches that `VECTOR` names that same vector.
+
+/// Driven directly by the vector [`bind_move!`] generates a handler for.
+pub trait InterruptState {
+    /// Called with exclusive access to the data every time the bound
+    /// interrupt fires.
+    fn on_interrupt(&mut self);
+}
+
+/// Ties a type to the name of the interrupt/exception vector its `Move` was
+/// constructed with, so [`bind_move!`] can check the two agree at compile
+/// time.
+///
+/// # Safety
+///
+/// The implementor must ensure `VECTOR` exactly matches the name of the
+/// interrupt or exception vector that the corresponding `Move`'s `Context`
+/// was constructed with. Getting this wrong defeats the whole point of
+/// `bind_move!`: the mismatch check passes, but `try_lock` still never sees
+/// the handler active and silently returns `Err(())`.
+pub unsafe trait InterruptBinding {
+    /// The name of the bound interrupt or exception vector, e.g. `"TIMER1"`.
+    const VECTOR: &'static str;
+}
+
+/// `const`-evaluable string equality, used by [`bind_move!`] to compare
+/// vector names at compile time (`&str`'s `PartialEq` isn't `const` yet).
+pub const fn vector_names_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Generates a `#[interrupt] fn $vector()` that locks `$move` and drives
+/// `$data`'s [`InterruptState::on_interrupt`], and statically asserts (via
+/// [`InterruptBinding`]) that `$data` was declared as bound to `$vector`.
+///
+/// ```ignore
+/// struct Timer1Data { /* ... */ }
+///
+/// impl InterruptState for Timer1Data {
+///     fn on_interrupt(&mut self) { /* ... */ }
+/// }
+///
+/// unsafe impl InterruptBinding for Timer1Data {
+///     const VECTOR: &'static str = "TIMER1";
+/// }
+///
+/// static TIMER_1_DATA: Move<Timer1Data, Interrupt> =
+///     Move::new_uninitialized(Context::Interrupt(Interrupt::TIMER1));
+///
+/// bind_move!(TIMER1, Timer1Data => TIMER_1_DATA);
+/// ```
+///
+/// A `bind_move!(TIMER2, Timer1Data => TIMER_1_DATA)` (wrong vector) fails
+/// to compile instead of silently installing a handler that never locks.
+/// So does a `$move` that isn't actually a `Move<Timer1Data, ..>` -- the
+/// generated handler's closure is annotated with `$data`, so `$move`'s own
+/// type has to agree with it too.
+#[macro_export]
+macro_rules! bind_move {
+    ($vector:ident, $data:ty => $move:path) => {
+        const _: () = assert!(
+            $crate::vector_names_eq(<$data as $crate::InterruptBinding>::VECTOR, stringify!($vector)),
+            concat!(
+                stringify!($data),
+                " is bound to a different vector than `bind_move!` was asked to generate"
+            ),
+        );
+
+        #[allow(non_snake_case)]
+        #[interrupt]
+        fn $vector() {
+            $move
+                // Annotating the closure parameter with `$data` (rather than
+                // letting it infer) ties `$move`'s own `T` to `$data`: if
+                // `$move` is a `Move` over some other type, this is a type
+                // mismatch, not a silent pass. Without this, the assert above
+                // only checks that *some* type named `$data` claims `$vector`
+                // -- it never confirms `$move` is actually a `Move` of that
+                // type, so a `Move` bound to the wrong vector could still
+                // compile and then silently never fire.
+                .try_lock(|data: &mut $data| $crate::InterruptState::on_interrupt(data))
+                .ok();
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_names_match() {
+        assert!(vector_names_eq("TIMER1", "TIMER1"));
+    }
+
+    #[test]
+    fn different_names_of_equal_length_dont_match() {
+        assert!(!vector_names_eq("TIMER1", "TIMER2"));
+    }
+
+    #[test]
+    fn different_length_names_dont_match() {
+        assert!(!vector_names_eq("TIMER1", "TIMER10"));
+        assert!(!vector_names_eq("TIMER10", "TIMER1"));
+    }
+
+    #[test]
+    fn empty_names_match_only_each_other() {
+        assert!(vector_names_eq("", ""));
+        assert!(!vector_names_eq("", "TIMER1"));
+    }
+}
@@ -14,14 +14,17 @@
 //!
 //! Here's how it works:
 //!
-//! ```rust, no_run
+//! ```rust, ignore
 //! #![no_main]
 //!
 //! // CMIM items
 //! use cmim::{
-//!     Move,
+//!     bind_move,
 //!     Context,
 //!     Exception,
+//!     InterruptBinding,
+//!     InterruptState,
+//!     Move,
 //! };
 //!
 //! // Used to set the program entry point
@@ -50,6 +53,35 @@
 //!     toggle: bool,
 //! }
 //!
+//! impl InterruptState for Timer1Data {
+//!     fn on_interrupt(&mut self) {
+//!         // Start the timer again first for accuracy
+//!         self.timer.cancel().unwrap();
+//!         self.timer.start(1_000_000u32);
+//!
+//!         // Write message to UART. The NRF UART requires data
+//!         // to be in RAM, not flash.
+//!         const MSG_BYTES: &[u8] = "Blink!\r\n".as_bytes();
+//!         let mut buf = [0u8; MSG_BYTES.len()];
+//!         buf.copy_from_slice(MSG_BYTES);
+//!
+//!         self.uart.write(&buf).unwrap();
+//!
+//!         // Blink the LED
+//!         if self.toggle {
+//!             self.led.enable();
+//!         } else {
+//!             self.led.disable();
+//!         }
+//!
+//!         self.toggle = !self.toggle;
+//!     }
+//! }
+//!
+//! unsafe impl InterruptBinding for Timer1Data {
+//!     const VECTOR: &'static str = "TIMER1";
+//! }
+//!
 //! struct SysTickData {
 //!     led: dwm1001::Led,
 //!     toggle: bool,
@@ -127,34 +159,24 @@
 //!         .ok();
 //! }
 //!
-//! #[interrupt]
-//! fn TIMER1() {
-//!     TIMER_1_DATA
-//!         .try_lock(|data| {
-//!             // Start the timer again first for accuracy
-//!             data.timer.cancel().unwrap();
-//!             data.timer.start(1_000_000u32);
+//! bind_move!(TIMER1, Timer1Data => TIMER_1_DATA);
+//! ```
 //!
-//!             // Write message to UART. The NRF UART requires data
-//!             // to be in RAM, not flash.
-//!             const MSG_BYTES: &[u8] = "Blink!\r\n".as_bytes();
-//!             let mut buf = [0u8; MSG_BYTES.len()];
-//!             buf.copy_from_slice(MSG_BYTES);
+//! `SysTick` is an exception rather than a device interrupt, so it's wired
+//! up by hand with `try_lock` here; [`bind_move!`] only generates
+//! `#[interrupt]` handlers. `TIMER1` shows the alternative: letting
+//! `bind_move!` generate the handler and check at compile time that
+//! `Timer1Data` and `TIMER_1_DATA` agree on which vector they're bound to.
 //!
-//!             data.uart.write(&buf).unwrap();
+//! A few other tools this crate provides, not shown above:
 //!
-//!             // Blink the LED
-//!             if data.toggle {
-//!                 data.led.enable();
-//!             } else {
-//!                 data.led.disable();
-//!             }
-//!
-//!             data.toggle = !data.toggle;
-//!         })
-//!         .ok();
-//! }
-//! ```
+//! - [`Move::lock_ceiling`] -- a priority-ceiling lock for data shared by
+//!   several contexts at different priorities, rather than owned by one.
+//! - [`Move::try_lock_ref`] -- a reentrant, shared-read counterpart to
+//!   `try_lock`, for a handler that wants to lend the data to a helper that
+//!   itself needs to borrow it.
+//! - [`MoveQueue`] -- a fixed-capacity queue for streaming messages into a
+//!   bound context instead of replacing its whole `T` at once.
 //!
 //!
 //! # License
@@ -174,47 +196,66 @@
 //! for inclusion in the work by you, as defined in the Apache-2.0 license, shall be
 //! dual licensed as above, without any additional terms or conditions.
 
-#![no_std]
+#![cfg_attr(not(test), no_std)]
+// `Err(())` throughout this crate means "the access you asked for isn't
+// currently valid" (wrong context, already locked, ...) -- there's no
+// payload worth giving it its own type over, and this is a `no_std` crate
+// where reaching for `alloc`-backed error machinery isn't idiomatic anyway.
+#![allow(clippy::result_unit_err)]
 
 use core::{
     cell::UnsafeCell,
-    cmp::PartialEq,
+    marker::PhantomData,
     mem::MaybeUninit,
     result::Result,
     sync::atomic::{AtomicU8, Ordering},
 };
 
 use bare_metal::Nr;
-use cortex_m::interrupt::free;
-use cortex_m::peripheral::{scb::VectActive, SCB};
+#[cfg(feature = "cortex-m")]
+use cortex_m::register::{basepri, basepri_max};
+#[cfg(feature = "cortex-m")]
 pub use cortex_m::peripheral::scb::Exception;
 
+pub mod arch;
+pub use arch::ContextArch;
+#[cfg(feature = "cortex-m")]
+pub use arch::CortexM;
+#[cfg(feature = "riscv")]
+pub use arch::Plic;
+
+mod bind;
+pub use bind::{vector_names_eq, InterruptBinding, InterruptState};
+
+mod queue;
+pub use queue::{Full, MoveQueue};
+
+#[cfg(test)]
+pub(crate) mod test_support;
+
 /// Context is the place where data will be moved to. This can be either
 /// interrupt context, or exception context
 pub enum Context<I> {
-    /// An Exception, such as SysTick. Re-exported from the `cortex-m` crate
+    /// An Exception, such as SysTick. Re-exported from the `cortex-m` crate.
+    /// Only meaningful on the Cortex-M backend.
+    #[cfg(feature = "cortex-m")]
     Exception(Exception),
 
     /// A device specific interrupt, as defined by a `-pac` crate
     Interrupt(I),
 }
 
-impl<I: Nr> PartialEq<VectActive> for Context<I> {
-    fn eq(&self, other: &VectActive) -> bool {
-        match (self, other) {
-            (Context::Exception(e_s), VectActive::Exception(e_o)) => e_s == e_o,
-            (Context::Interrupt(i_s), VectActive::Interrupt{ irqn }) => i_s.nr() == *irqn,
-            _ => false,
-        }
-    }
-}
-
 /// Move is a structure that is intended to be stored as a static variable,
 /// and represents a metaphorical "move" to an interrupt context. Data is moved
 /// to the interrupt context by calling `try_move` from thread (non-interrupt)
 /// context, and the data can be retrived within a selected interrupt using the
 /// `try_lock` method.
-pub struct Move<T, I> {
+///
+/// `A` is the architecture backend (see [`ContextArch`]) used to detect the
+/// active interrupt/exception. It defaults to [`CortexM`] for source
+/// compatibility with code written against earlier versions of this crate.
+#[cfg(feature = "cortex-m")]
+pub struct Move<T, I, A = CortexM> {
     /// `data` contains the user data, which may or may not be initialized
     data: UnsafeCell<MaybeUninit<T>>,
 
@@ -222,16 +263,35 @@ pub struct Move<T, I> {
     state: AtomicU8,
 
     context: Context<I>,
+
+    /// The BASEPRI value (in the board's NVIC priority encoding) that
+    /// `lock_ceiling` raises to. `0` means "no ceiling configured", which
+    /// makes `lock_ceiling` a no-op critical section, matching ARM's own
+    /// treatment of BASEPRI=0 as "masking disabled".
+    ceiling: u8,
+
+    _arch: PhantomData<A>,
+}
+
+/// See [`Move`]; this is the definition used when the `cortex-m` feature
+/// (and therefore its default arch backend) is disabled, so callers must
+/// name their backend explicitly, e.g. `Move<T, I, Plic>`.
+#[cfg(not(feature = "cortex-m"))]
+pub struct Move<T, I, A> {
+    data: UnsafeCell<MaybeUninit<T>>,
+    state: AtomicU8,
+    context: Context<I>,
+    _arch: PhantomData<A>,
 }
 
-unsafe impl<T, I> Sync for Move<T, I>
+unsafe impl<T, I, A> Sync for Move<T, I, A>
 where
     T: Send + Sized,
     I: Nr,
 {
 }
 
-impl<T, I> Move<T, I> {
+impl<T, I, A> Move<T, I, A> {
     /// The data is uninitialized
     const UNINIT: u8 = 0;
 
@@ -241,6 +301,23 @@ impl<T, I> Move<T, I> {
     /// The data is initialized, but currently locked by an interrupt
     const LOCKED: u8 = 2;
 
+    /// The data is transiently being written by `try_move`/`try_free`. This
+    /// is a narrower critical section than a global interrupt disable: a
+    /// preempting `try_lock` sees `WRITING` (not `INIT_AND_IDLE`) and fails
+    /// exactly as it would for `LOCKED`, so it can never observe a torn write.
+    const WRITING: u8 = 3;
+
+    /// The data is initialized and borrowed by one or more `try_lock_ref`
+    /// readers. States `READING_BASE..=u8::MAX` all mean "reading", with the
+    /// value minus `READING_BASE` giving the current reader depth; this lets
+    /// `try_lock_ref` nest without a separate counter field. `try_lock` must
+    /// treat any of these states the same as `LOCKED`.
+    const READING_BASE: u8 = 4;
+
+    /// The deepest reader nesting `try_lock_ref` supports before it reports
+    /// an error instead of wrapping the depth counter.
+    const MAX_READ_DEPTH: u8 = u8::MAX - Self::READING_BASE;
+
     /// Create a new `Move` structure without initializing the data contained by it.
     /// This is best used when the data cannot be initialized until runtime, such as
     /// a HAL peripheral, or the producer or consumer of a queue.
@@ -251,11 +328,7 @@ impl<T, I> Move<T, I> {
     /// You must provide the context that is allowed to later access this data
     /// as the `ctxt` argument
     pub const fn new_uninitialized(ctxt: Context<I>) -> Self {
-        Move {
-            data: UnsafeCell::new(MaybeUninit::uninit()),
-            state: AtomicU8::new(Self::UNINIT),
-            context: ctxt,
-        }
+        Self::new_uninitialized_with_ceiling(ctxt, 0)
     }
 
      /// Create a new `Move` structure, and initialize the data contained within it.
@@ -267,45 +340,89 @@ impl<T, I> Move<T, I> {
      /// You must provide the context that is allowed to later access this data
      /// as the `ctxt` argument
     pub const fn new(data: T, ctxt: Context<I>) -> Self {
+        Self::new_with_ceiling(data, ctxt, 0)
+    }
+
+    /// Like [`new_uninitialized`](Self::new_uninitialized), but also declares the
+    /// priority ceiling (in the board's NVIC priority encoding, numerically lower
+    /// is more urgent) used by [`lock_ceiling`](Self::lock_ceiling).
+    ///
+    /// The ceiling should be the numerically-highest-urgency priority among every
+    /// context that is allowed to share this data via `lock_ceiling`.
+    pub const fn new_uninitialized_with_ceiling(ctxt: Context<I>, ceiling: u8) -> Self {
+        // On non-cortex-m backends nothing reads `ceiling` (`lock_ceiling` is
+        // cortex-m-only), so the field itself is dropped rather than kept
+        // around unread.
+        #[cfg(not(feature = "cortex-m"))]
+        let _ = ceiling;
+
         Move {
-            data: UnsafeCell::new(MaybeUninit::new(data)),
+            data: UnsafeCell::new(MaybeUninit::uninit()),
             state: AtomicU8::new(Self::UNINIT),
             context: ctxt,
+            #[cfg(feature = "cortex-m")]
+            ceiling,
+            _arch: PhantomData,
+        }
+    }
+
+    /// Like [`new`](Self::new), but also declares the priority ceiling (in the
+    /// board's NVIC priority encoding, numerically lower is more urgent) used by
+    /// [`lock_ceiling`](Self::lock_ceiling).
+    ///
+    /// The ceiling should be the numerically-highest-urgency priority among every
+    /// context that is allowed to share this data via `lock_ceiling`.
+    pub const fn new_with_ceiling(data: T, ctxt: Context<I>, ceiling: u8) -> Self {
+        #[cfg(not(feature = "cortex-m"))]
+        let _ = ceiling;
+
+        Move {
+            data: UnsafeCell::new(MaybeUninit::new(data)),
+            state: AtomicU8::new(Self::INIT_AND_IDLE),
+            context: ctxt,
+            #[cfg(feature = "cortex-m")]
+            ceiling,
+            _arch: PhantomData,
         }
     }
 }
 
-impl<T, I> Move<T, I>
+impl<T, I, A> Move<T, I, A>
 where
     T: Send + Sized,
     I: Nr,
+    A: ContextArch<I>,
 {
     /// Attempt to initialize the data of the `Move` structure.
-    /// This *MUST* be called from non-interrupt context, and a critical
-    /// section will be in place while setting the data.
+    /// This *MUST* be called from non-interrupt context.
+    ///
+    /// Rather than a global interrupt disable, this uses a compare-exchange
+    /// protocol on `state`: we CAS into the transient `WRITING` state before
+    /// touching `data`, and only publish `INIT_AND_IDLE` once the write is
+    /// complete. If the destination interrupt preempts mid-write, `try_lock`
+    /// observes `WRITING` (not `INIT_AND_IDLE`) and fails exactly as it
+    /// would for `LOCKED`, so it can never see a half-initialized `T`.
     ///
     /// Returns:
     ///
     /// * Ok(Some(T)): If we are in thread mode and the data was previously initialized
     /// * Ok(None): If we are in thread mode and the data was not previously initialized
     /// * Err(T): If we are not in thread mode (e.g. an interrupt is active), return the
-    ///     data that was going to be moved
+    ///   data that was going to be moved
     pub fn try_move(&self, data: T) -> Result<Option<T>, T> {
-        free(|_cs| {
-            // Check if we are in non-interrupt context
-            match SCB::vect_active() {
-                // TODO: Would it be reasonable to initialize this from a DIFFERENT
-                // interrupt context? Basically anything but the destination interrupt?
-                VectActive::ThreadMode => {}
-                _ => {
-                    return Err(data);
-                }
-            }
+        // Check if we are in non-interrupt context
+        // TODO: Would it be reasonable to initialize this from a DIFFERENT
+        // interrupt context? Basically anything but the destination interrupt?
+        if !A::is_thread_mode() {
+            return Err(data);
+        }
 
-            // Since we are in a critical section, it is not necessary to perform
-            // an atomic compare and swap, as we cannot be pre-empted
-            match self.state.load(Ordering::SeqCst) {
-                Self::UNINIT => {
+        loop {
+            match self
+                .state
+                .compare_exchange_weak(Self::UNINIT, Self::WRITING, Ordering::Acquire, Ordering::Relaxed)
+            {
+                Ok(_) => {
                     unsafe {
                         // Reference to an uninitialized MaybeUninit
                         let mu_ref = &mut *self.data.get();
@@ -315,10 +432,26 @@ where
                         let dat_ptr = mu_ref.as_mut_ptr();
                         dat_ptr.write(data);
                     }
-                    self.state.store(Self::INIT_AND_IDLE, Ordering::SeqCst);
-                    Ok(None)
+                    self.state.store(Self::INIT_AND_IDLE, Ordering::Release);
+                    return Ok(None);
                 }
-                Self::INIT_AND_IDLE => {
+                // Spurious failure of the weak CAS: state was still UNINIT, retry.
+                Err(Self::UNINIT) => continue,
+                Err(Self::INIT_AND_IDLE) => break,
+                Err(_) => return Err(data),
+            }
+        }
+
+        // The replace path: UNINIT -> WRITING didn't apply because we were
+        // already INIT_AND_IDLE, so try that transition instead.
+        loop {
+            match self.state.compare_exchange_weak(
+                Self::INIT_AND_IDLE,
+                Self::WRITING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
                     let old = unsafe {
                         // Reference to an initialized MaybeUninit
                         let mu_ref = &mut *self.data.get();
@@ -329,16 +462,21 @@ where
                         let dat_ptr = mu_ref.as_mut_ptr();
                         dat_ptr.replace(data)
                     };
-                    Ok(Some(old))
+                    self.state.store(Self::INIT_AND_IDLE, Ordering::Release);
+                    return Ok(Some(old));
                 }
-                Self::LOCKED | _ => Err(data),
+                // Spurious failure of the weak CAS: state was still INIT_AND_IDLE, retry.
+                Err(Self::INIT_AND_IDLE) => continue,
+                Err(_) => return Err(data),
             }
-        })
+        }
     }
 
     /// Attempt to recover the data from the `Move` structure.
-    /// This *MUST* be called from non-interrupt context, and a critical
-    /// section will be in place while receiving the data.
+    /// This *MUST* be called from non-interrupt context.
+    ///
+    /// Uses the same lock-free compare-exchange protocol as `try_move`
+    /// instead of a global interrupt disable; see its docs for details.
     ///
     /// Returns:
     ///
@@ -346,22 +484,21 @@ where
     /// * Ok(None): If we are in thread mode and the data was not previously initialized
     /// * Err(()): If we are not in thread mode (e.g. an interrupt is active)
     pub fn try_free(&self) -> Result<Option<T>, ()> {
-        free(|_cs| {
-            // Check if we are in non-interrupt context
-            match SCB::vect_active() {
-                // TODO: Would it be reasonable to free this from a DIFFERENT
-                // interrupt context? Basically anything but the destination interrupt?
-                VectActive::ThreadMode => {}
-                _ => {
-                    return Err(());
-                }
-            }
+        // Check if we are in non-interrupt context
+        // TODO: Would it be reasonable to free this from a DIFFERENT
+        // interrupt context? Basically anything but the destination interrupt?
+        if !A::is_thread_mode() {
+            return Err(());
+        }
 
-            // Since we are in a critical section, it is not necessary to perform
-            // an atomic compare and swap, as we cannot be pre-empted
-            match self.state.load(Ordering::SeqCst) {
-                Self::UNINIT => Ok(None),
-                Self::INIT_AND_IDLE => {
+        loop {
+            match self.state.compare_exchange_weak(
+                Self::INIT_AND_IDLE,
+                Self::WRITING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
                     let old = unsafe {
                         // Get a pointer to the initialized data
                         let mu_ptr = self.data.get();
@@ -371,13 +508,16 @@ where
                         mu_ptr.replace(MaybeUninit::uninit()).assume_init()
                     };
 
-                    self.state.store(Self::UNINIT, Ordering::SeqCst);
+                    self.state.store(Self::UNINIT, Ordering::Release);
 
-                    Ok(Some(old))
+                    return Ok(Some(old));
                 }
-                Self::LOCKED | _ => Err(()),
+                // Spurious failure of the weak CAS: state was still INIT_AND_IDLE, retry.
+                Err(Self::INIT_AND_IDLE) => continue,
+                Err(Self::UNINIT) => return Ok(None),
+                Err(_) => return Err(()),
             }
-        })
+        }
     }
 
     /// So, this isn't a classical mutex. It will *only* provide access if:
@@ -388,7 +528,7 @@ where
     /// If these conditions are met, then you can access the variable from within
     /// a closure
     pub fn try_lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R, ()> {
-        if self.context != SCB::vect_active() {
+        if !A::is_active(&self.context) {
             return Err(());
         }
 
@@ -429,9 +569,336 @@ where
                 Ok(ret)
             }
 
-            // The data is locked, or the status register is garbage.
-            // Don't provide access
-            Self::LOCKED | _ => Err(()),
+            // The data is locked (or a `try_lock_ref` borrow is outstanding).
+            // Don't provide access.
+            _ => Err(()),
         }
     }
+
+    /// A reentrant, shared-read counterpart to `try_lock`. Where `try_lock`
+    /// hands out an exclusive `&mut T` and refuses to nest, `try_lock_ref`
+    /// hands out a shared `&T` and allows nested calls from the same
+    /// (active) context -- aliasing `&T` is sound, so a helper called from
+    /// within the closure can itself call `try_lock_ref` again.
+    ///
+    /// `try_lock` still fails while any `try_lock_ref` borrow is
+    /// outstanding, and `try_lock_ref` still fails while `try_lock` holds
+    /// the exclusive borrow: the two are mutually exclusive, just like a
+    /// `RwLock`.
+    ///
+    /// Nesting depth is tracked in `state` itself and saturates at
+    /// `u8::MAX - READING_BASE`; exceeding it returns `Err(())` rather than
+    /// wrapping the counter.
+    pub fn try_lock_ref<R>(&self, f: impl FnOnce(&T) -> R) -> Result<R, ()> {
+        if !A::is_active(&self.context) {
+            return Err(());
+        }
+
+        loop {
+            let current = self.state.load(Ordering::Acquire);
+            let next = match current {
+                Self::INIT_AND_IDLE => Self::READING_BASE,
+                n if n >= Self::READING_BASE => {
+                    if n - Self::READING_BASE >= Self::MAX_READ_DEPTH {
+                        return Err(());
+                    }
+                    n + 1
+                }
+                _ => return Err(()),
+            };
+
+            if self
+                .state
+                .compare_exchange_weak(current, next, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        let dat_ref = unsafe {
+            // Create a shared reference to an initialized MaybeUninit. Other
+            // `&T` borrows may be live at the same time (that's the point),
+            // but the depth counter above guarantees no `&mut T` is.
+            let mu_ref = &*self.data.get();
+            let dat_ptr = mu_ref.as_ptr();
+            &*dat_ptr
+        };
+
+        let ret = f(dat_ref);
+
+        loop {
+            let current = self.state.load(Ordering::Acquire);
+            let next = if current == Self::READING_BASE {
+                Self::INIT_AND_IDLE
+            } else {
+                current - 1
+            };
+
+            if self
+                .state
+                .compare_exchange_weak(current, next, Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        Ok(ret)
+    }
+}
+
+#[cfg(feature = "cortex-m")]
+impl<T, I> Move<T, I, CortexM>
+where
+    T: Send + Sized,
+    I: Nr,
+{
+    /// A priority-ceiling lock, for data shared between several contexts at
+    /// different priorities (e.g. two interrupts plus thread mode), rather
+    /// than owned by a single bound `Context`.
+    ///
+    /// Unlike `try_lock`, this doesn't check which vector is currently
+    /// active. Instead it raises `BASEPRI` to the `ceiling` given to
+    /// `new_with_ceiling`/`new_uninitialized_with_ceiling` for the duration
+    /// of the closure, masking every sharing context at or below that
+    /// priority so they can't preempt and observe a torn write, then
+    /// restores the previous `BASEPRI`. This is the RTIC priority-ceiling
+    /// protocol: contexts above the ceiling are still free to preempt, so
+    /// you only pay for as much masking as the sharing actually requires.
+    ///
+    /// The `LOCKED` re-entrancy guard still applies, so a nested call (e.g.
+    /// via a helper called from within the closure) returns `Err(())`
+    /// instead of creating a second `&mut T`.
+    ///
+    /// `lock_ceiling` raises `BASEPRI` directly, so it's only available on
+    /// the [`CortexM`] backend.
+    pub fn lock_ceiling<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R, ()> {
+        match self.state.load(Ordering::SeqCst) {
+            Self::INIT_AND_IDLE => {}
+            _ => return Err(()),
+        }
+
+        self.state.store(Self::LOCKED, Ordering::SeqCst);
+
+        // `basepri_max` only raises the mask (it's a no-op if `ceiling` is
+        // less urgent than the current BASEPRI, or if `ceiling` is 0), so we
+        // never need to compute the min ourselves.
+        let restore = basepri::read();
+        basepri_max::write(self.ceiling);
+
+        let dat_ref = unsafe {
+            let mu_ref = &mut *self.data.get();
+            let dat_ptr = mu_ref.as_mut_ptr();
+            &mut *dat_ptr
+        };
+
+        let ret = f(dat_ref);
+
+        unsafe {
+            basepri::write(restore);
+        }
+
+        self.state.store(Self::INIT_AND_IDLE, Ordering::SeqCst);
+
+        Ok(ret)
+    }
+}
+
+/// Model-checks the lock-free state machine behind `try_move` and
+/// `try_lock` (`UNINIT -> WRITING -> INIT_AND_IDLE`, and
+/// `INIT_AND_IDLE -> LOCKED -> INIT_AND_IDLE`) under `loom`, including
+/// spurious `compare_exchange_weak` failures, to prove no torn reads or lost
+/// ownership are possible. `try_free`'s `INIT_AND_IDLE -> WRITING -> UNINIT`
+/// path and `try_move`'s replace path are not modelled.
+///
+/// `Move` itself uses `core` atomics, which `loom` cannot instrument, so this
+/// models the state machine directly against a `loom` `AtomicU8` rather than
+/// driving the public `Move` API.
+///
+/// Run with: `RUSTFLAGS="--cfg loom" cargo test --release` (loom's
+/// interleaving exploration is too slow for a debug build).
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use loom::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+    use loom::sync::Arc;
+    use loom::thread;
+
+    const UNINIT: u8 = 0;
+    const INIT_AND_IDLE: u8 = 1;
+    const LOCKED: u8 = 2;
+    const WRITING: u8 = 3;
+
+    /// Mirrors the `try_move` CAS loop: UNINIT -> WRITING -> INIT_AND_IDLE.
+    fn try_write(state: &AtomicU8, value: &AtomicUsize, v: usize) -> bool {
+        loop {
+            match state.compare_exchange_weak(UNINIT, WRITING, Ordering::Acquire, Ordering::Relaxed) {
+                Ok(_) => {
+                    value.store(v, Ordering::Relaxed);
+                    state.store(INIT_AND_IDLE, Ordering::Release);
+                    return true;
+                }
+                // Spurious weak-CAS failure: state was still UNINIT, retry.
+                Err(UNINIT) => continue,
+                Err(_) => return false,
+            }
+        }
+    }
+
+    /// Mirrors the `try_lock` transition: INIT_AND_IDLE -> LOCKED -> INIT_AND_IDLE.
+    fn try_lock(state: &AtomicU8, value: &AtomicUsize) -> Option<usize> {
+        match state.compare_exchange(INIT_AND_IDLE, LOCKED, Ordering::Acquire, Ordering::Relaxed) {
+            Ok(_) => {
+                let seen = value.load(Ordering::Relaxed);
+                state.store(INIT_AND_IDLE, Ordering::Release);
+                Some(seen)
+            }
+            Err(_) => None,
+        }
+    }
+
+    #[test]
+    fn concurrent_write_and_lock_never_tears() {
+        loom::model(|| {
+            let state = Arc::new(AtomicU8::new(UNINIT));
+            let value = Arc::new(AtomicUsize::new(0));
+
+            let (s1, v1) = (state.clone(), value.clone());
+            let writer = thread::spawn(move || {
+                try_write(&s1, &v1, 0xAA);
+            });
+
+            let (s2, v2) = (state.clone(), value.clone());
+            let locker = thread::spawn(move || {
+                // If the lock succeeds, it must have observed a fully
+                // published write (0xAA) -- never a torn or default value,
+                // i.e. a successful lock never overlaps a `WRITING` state.
+                if let Some(seen) = try_lock(&s2, &v2) {
+                    assert_eq!(seen, 0xAA);
+                }
+            });
+
+            writer.join().unwrap();
+            locker.join().unwrap();
+        });
+    }
+}
+
+/// `lock_ceiling` itself can't be exercised here: it reads and writes the
+/// real `BASEPRI` register, which only exists on Cortex-M hardware and would
+/// be unsound to touch from a host test binary. This just checks the
+/// ceiling value a `Move` is constructed with is the one `lock_ceiling`
+/// would later see.
+#[cfg(all(test, feature = "cortex-m"))]
+mod ceiling_tests {
+    use super::*;
+
+    struct DummyInterrupt(u8);
+
+    unsafe impl Nr for DummyInterrupt {
+        fn nr(&self) -> u8 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn new_with_ceiling_stores_the_ceiling() {
+        let m: Move<u32, DummyInterrupt, CortexM> =
+            Move::new_with_ceiling(7, Context::Interrupt(DummyInterrupt(1)), 0x40);
+        assert_eq!(m.ceiling, 0x40);
+    }
+
+    #[test]
+    fn new_uninitialized_with_ceiling_stores_the_ceiling() {
+        let m: Move<u32, DummyInterrupt, CortexM> =
+            Move::new_uninitialized_with_ceiling(Context::Interrupt(DummyInterrupt(1)), 0x60);
+        assert_eq!(m.ceiling, 0x60);
+    }
+
+    #[test]
+    fn new_and_new_uninitialized_default_to_ceiling_zero() {
+        let m: Move<u32, DummyInterrupt, CortexM> = Move::new(7, Context::Interrupt(DummyInterrupt(1)));
+        assert_eq!(m.ceiling, 0);
+
+        let m: Move<u32, DummyInterrupt, CortexM> =
+            Move::new_uninitialized(Context::Interrupt(DummyInterrupt(1)));
+        assert_eq!(m.ceiling, 0);
+    }
+}
+
+/// `try_lock_ref`'s reentrancy/mutual-exclusion logic lives entirely in
+/// `state`, so it can be driven on the host with [`test_support::TestArch`]
+/// standing in for a real interrupt.
+#[cfg(test)]
+mod try_lock_ref_tests {
+    use super::*;
+    use crate::test_support::{DummyInterrupt, TestArch};
+
+    fn new_move() -> Move<u32, DummyInterrupt, TestArch> {
+        Move::new(7, Context::Interrupt(DummyInterrupt(1)))
+    }
+
+    #[test]
+    fn try_lock_ref_rejects_outside_bound_context() {
+        let m = new_move();
+        assert!(m.try_lock_ref(|_| ()).is_err());
+    }
+
+    #[test]
+    fn try_lock_ref_sees_the_value() {
+        let m = new_move();
+        TestArch::enter(1);
+        assert_eq!(m.try_lock_ref(|v| *v).unwrap(), 7);
+        TestArch::leave();
+    }
+
+    #[test]
+    fn try_lock_ref_nests() {
+        let m = new_move();
+        TestArch::enter(1);
+        m.try_lock_ref(|_| {
+            m.try_lock_ref(|_| {
+                m.try_lock_ref(|v| assert_eq!(*v, 7)).unwrap();
+            })
+            .unwrap();
+        })
+        .unwrap();
+        TestArch::leave();
+    }
+
+    #[test]
+    fn try_lock_excludes_outstanding_try_lock_ref() {
+        let m = new_move();
+        TestArch::enter(1);
+        m.try_lock_ref(|_| {
+            assert!(m.try_lock(|_| ()).is_err());
+        })
+        .unwrap();
+        // Once the borrow is released, try_lock works again.
+        assert!(m.try_lock(|_| ()).is_ok());
+        TestArch::leave();
+    }
+
+    #[test]
+    fn try_lock_ref_excludes_outstanding_try_lock() {
+        let m = new_move();
+        TestArch::enter(1);
+        m.try_lock(|_| {
+            assert!(m.try_lock_ref(|_| ()).is_err());
+        })
+        .unwrap();
+        assert!(m.try_lock_ref(|_| ()).is_ok());
+        TestArch::leave();
+    }
+
+    #[test]
+    fn try_lock_ref_saturates_at_max_depth() {
+        let m = new_move();
+        TestArch::enter(1);
+        // Drive straight to the deepest depth without actually recursing
+        // Move::MAX_READ_DEPTH times.
+        m.state.store(u8::MAX, Ordering::SeqCst);
+        assert!(m.try_lock_ref(|_| ()).is_err());
+        TestArch::leave();
+    }
 }
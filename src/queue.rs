@@ -0,0 +1,230 @@
+//! A fixed-capacity single-producer/single-consumer queue, for thread-mode
+//! code that wants to post a stream of messages into an interrupt rather
+//! than replace the whole `T` via [`Move::try_move`](crate::Move::try_move).
+
+use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use bare_metal::Nr;
+
+use crate::{Context, ContextArch};
+#[cfg(feature = "cortex-m")]
+use crate::CortexM;
+
+/// Returned by [`MoveQueue::try_post`] when the queue has no free slot, or
+/// when called outside thread mode (which would violate the single-producer
+/// invariant).
+#[derive(Debug)]
+pub struct Full;
+
+/// A ring buffer backed by an `N`-element array, owned by a single
+/// interrupt/exception `Context` the same way [`Move`](crate::Move) is.
+/// Thread-mode code enqueues with [`try_post`](Self::try_post); the bound
+/// context dequeues everything waiting with [`try_drain`](Self::try_drain).
+///
+/// Only `N - 1` messages can be queued at once: the ring buffer tells "empty"
+/// from "full" by never letting `head` catch up to `tail`, so one slot always
+/// stays unused.
+///
+/// Like `Move`, `A` is the architecture backend (see [`ContextArch`]) used to
+/// detect the active context, defaulting to [`CortexM`] for source
+/// compatibility.
+#[cfg(feature = "cortex-m")]
+pub struct MoveQueue<T, const N: usize, I, A = CortexM> {
+    buf: UnsafeCell<[MaybeUninit<T>; N]>,
+
+    /// Next slot the producer will write to. Written only by the producer,
+    /// read by both; published with `Release` after the slot is filled.
+    head: AtomicUsize,
+
+    /// Next slot the consumer will read from. Written only by the consumer,
+    /// read by both; published with `Release` after the slot is vacated.
+    tail: AtomicUsize,
+
+    context: Context<I>,
+    _arch: PhantomData<A>,
+}
+
+/// See [`MoveQueue`]; used when the `cortex-m` feature is disabled, so
+/// callers must name their backend explicitly.
+#[cfg(not(feature = "cortex-m"))]
+pub struct MoveQueue<T, const N: usize, I, A> {
+    buf: UnsafeCell<[MaybeUninit<T>; N]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    context: Context<I>,
+    _arch: PhantomData<A>,
+}
+
+unsafe impl<T, const N: usize, I, A> Sync for MoveQueue<T, N, I, A>
+where
+    T: Send + Sized,
+    I: Nr,
+{
+}
+
+impl<T, const N: usize, I, A> MoveQueue<T, N, I, A> {
+    /// Create a new, empty queue. You must provide the context that is
+    /// allowed to later drain this queue as the `ctxt` argument, the same as
+    /// `Move::new_uninitialized`.
+    pub const fn new(ctxt: Context<I>) -> Self {
+        MoveQueue {
+            // An array of `MaybeUninit<T>` never needs initializing, for any
+            // `T` -- every element already permits an arbitrary bit pattern.
+            buf: UnsafeCell::new(unsafe { MaybeUninit::uninit().assume_init() }),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            context: ctxt,
+            _arch: PhantomData,
+        }
+    }
+}
+
+impl<T, const N: usize, I, A> MoveQueue<T, N, I, A>
+where
+    T: Send + Sized,
+    I: Nr,
+    A: ContextArch<I>,
+{
+    /// Enqueue a message from thread (non-interrupt) context, without a
+    /// global critical section: the producer only ever advances `head`
+    /// after the slot it just wrote is fully initialized, so the consumer
+    /// (gated on the bound `Context` being active) can never observe a torn
+    /// write.
+    ///
+    /// This *MUST* be called from thread mode, the same restriction
+    /// `Move::try_move` places on its producer: the ring buffer's `head`
+    /// update is a plain load/store, not a CAS, so it only stays race-free
+    /// with a single producer. Checking only "not the bound consumer" would
+    /// still let two different non-consumer contexts (e.g. thread mode and
+    /// an unrelated lower-priority interrupt) race on it.
+    ///
+    /// Returns `Err(Full)` if the queue has no free slot, or if called
+    /// outside thread mode.
+    pub fn try_post(&self, msg: T) -> Result<(), Full> {
+        if !A::is_thread_mode() {
+            return Err(Full);
+        }
+
+        let head = self.head.load(Ordering::Relaxed);
+        let next_head = (head + 1) % N;
+        let tail = self.tail.load(Ordering::Acquire);
+
+        if next_head == tail {
+            return Err(Full);
+        }
+
+        unsafe {
+            (&mut *self.buf.get())[head].write(msg);
+        }
+
+        self.head.store(next_head, Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Drain every message currently queued, calling `f` with each in FIFO
+    /// order. Only callable from the bound `Context`, preserving the
+    /// single-consumer invariant.
+    ///
+    /// Returns the number of messages drained, or `Err(())` if called
+    /// outside the bound context.
+    pub fn try_drain(&self, mut f: impl FnMut(T)) -> Result<usize, ()> {
+        if !A::is_active(&self.context) {
+            return Err(());
+        }
+
+        let mut drained = 0;
+
+        loop {
+            let tail = self.tail.load(Ordering::Relaxed);
+            let head = self.head.load(Ordering::Acquire);
+
+            if tail == head {
+                break;
+            }
+
+            let msg = unsafe { (&*self.buf.get())[tail].assume_init_read() };
+
+            self.tail.store((tail + 1) % N, Ordering::Release);
+
+            f(msg);
+            drained += 1;
+        }
+
+        Ok(drained)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{DummyInterrupt, TestArch};
+
+    fn new_queue<const N: usize>() -> MoveQueue<u32, N, DummyInterrupt, TestArch> {
+        MoveQueue::new(Context::Interrupt(DummyInterrupt(1)))
+    }
+
+    #[test]
+    fn post_and_drain_preserve_fifo_order() {
+        let q = new_queue::<4>();
+        q.try_post(1).unwrap();
+        q.try_post(2).unwrap();
+        q.try_post(3).unwrap();
+
+        TestArch::enter(1);
+        let mut seen = Vec::new();
+        let drained = q.try_drain(|m| seen.push(m)).unwrap();
+        TestArch::leave();
+
+        assert_eq!(drained, 3);
+        assert_eq!(seen, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn try_post_rejects_outside_thread_mode() {
+        let q = new_queue::<4>();
+
+        // The bound consuming context...
+        TestArch::enter(1);
+        assert!(q.try_post(1).is_err());
+        TestArch::leave();
+
+        // ...and any other interrupt context, not just the consumer.
+        TestArch::enter(2);
+        assert!(q.try_post(1).is_err());
+        TestArch::leave();
+    }
+
+    #[test]
+    fn try_drain_rejects_outside_bound_context() {
+        let q = new_queue::<4>();
+        q.try_post(1).unwrap();
+
+        assert!(q.try_drain(|_| ()).is_err());
+
+        TestArch::enter(2);
+        assert!(q.try_drain(|_| ()).is_err());
+        TestArch::leave();
+    }
+
+    #[test]
+    fn try_post_reports_full_at_capacity() {
+        // Capacity N holds N - 1 usable slots: the ring buffer distinguishes
+        // "empty" from "full" by never letting head catch up to tail.
+        let q = new_queue::<2>();
+        q.try_post(1).unwrap();
+        assert!(q.try_post(2).is_err());
+
+        TestArch::enter(1);
+        let mut seen = Vec::new();
+        q.try_drain(|m| seen.push(m)).unwrap();
+        TestArch::leave();
+        assert_eq!(seen, vec![1]);
+
+        // Draining freed the slot back up.
+        q.try_post(2).unwrap();
+    }
+}
@@ -0,0 +1,54 @@
+//! A host-testable [`ContextArch`] backend, standing in for real hardware
+//! (`CortexM`/`Plic`) so `Move`/`MoveQueue`'s logic can be driven
+//! deterministically from `cargo test` instead of needing actual interrupts.
+
+use std::cell::Cell;
+
+use bare_metal::Nr;
+
+use crate::{Context, ContextArch};
+
+/// A minimal `Nr` implementor for tests, distinguished only by number.
+pub struct DummyInterrupt(pub u8);
+
+unsafe impl Nr for DummyInterrupt {
+    fn nr(&self) -> u8 {
+        self.0
+    }
+}
+
+thread_local! {
+    /// `None` means thread mode; `Some(n)` means interrupt `n` is active.
+    /// Thread-local so tests running on separate threads don't interfere.
+    static ACTIVE: Cell<Option<u8>> = const { Cell::new(None) };
+}
+
+/// The mock backend itself. Tests drive "which context is active" with
+/// [`TestArch::enter`]/[`TestArch::leave`] instead of a real interrupt firing.
+pub struct TestArch;
+
+impl TestArch {
+    /// Simulate interrupt `irqn` becoming active on this thread.
+    pub fn enter(irqn: u8) {
+        ACTIVE.with(|a| a.set(Some(irqn)));
+    }
+
+    /// Simulate returning to thread mode on this thread.
+    pub fn leave() {
+        ACTIVE.with(|a| a.set(None));
+    }
+}
+
+impl ContextArch<DummyInterrupt> for TestArch {
+    fn is_thread_mode() -> bool {
+        ACTIVE.with(|a| a.get().is_none())
+    }
+
+    fn is_active(ctxt: &Context<DummyInterrupt>) -> bool {
+        match ctxt {
+            Context::Interrupt(i) => ACTIVE.with(|a| a.get() == Some(i.nr())),
+            #[cfg(feature = "cortex-m")]
+            Context::Exception(_) => false,
+        }
+    }
+}